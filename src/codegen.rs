@@ -0,0 +1,482 @@
+//! Pluggable code generation from the struct model, used by
+//! [`crate::workspace_panels::CodePreviewPanel`] to render the struct as
+//! Rust, C, TypeScript, or JSON Schema.
+
+use crate::editor::{FieldDef, StructModel};
+use crate::field_editor::PRIMITIVE_TYPES;
+
+/// A code generation target selectable in the preview panel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CodeGenTarget {
+    Rust,
+    C,
+    TypeScript,
+    JsonSchema,
+}
+
+impl CodeGenTarget {
+    pub const ALL: [CodeGenTarget; 4] = [
+        CodeGenTarget::Rust,
+        CodeGenTarget::C,
+        CodeGenTarget::TypeScript,
+        CodeGenTarget::JsonSchema,
+    ];
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            CodeGenTarget::Rust => "Rust",
+            CodeGenTarget::C => "C",
+            CodeGenTarget::TypeScript => "TypeScript",
+            CodeGenTarget::JsonSchema => "JSON Schema",
+        }
+    }
+
+    fn generator(&self) -> Box<dyn CodeGenerator> {
+        match self {
+            CodeGenTarget::Rust => Box::new(RustGenerator),
+            CodeGenTarget::C => Box::new(CGenerator),
+            CodeGenTarget::TypeScript => Box::new(TypeScriptGenerator),
+            CodeGenTarget::JsonSchema => Box::new(JsonSchemaGenerator),
+        }
+    }
+
+    /// Renders `model` for this target.
+    pub fn generate(&self, model: &StructModel) -> String {
+        self.generator().generate(model)
+    }
+}
+
+impl std::str::FromStr for CodeGenTarget {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "rust" => Ok(CodeGenTarget::Rust),
+            "c" => Ok(CodeGenTarget::C),
+            "typescript" => Ok(CodeGenTarget::TypeScript),
+            "json-schema" => Ok(CodeGenTarget::JsonSchema),
+            other => Err(format!("unknown code generation target: {other}")),
+        }
+    }
+}
+
+/// Renders a [`StructModel`] into a language-specific source snippet.
+///
+/// Implementations must handle fields whose type references another
+/// workspace struct (i.e. isn't one of [`PRIMITIVE_TYPES`]) by emitting the
+/// referenced type name directly, plus whatever import/include the
+/// language needs.
+trait CodeGenerator {
+    fn generate(&self, model: &StructModel) -> String;
+}
+
+fn is_primitive(type_name: &str) -> bool {
+    PRIMITIVE_TYPES.contains(&type_name)
+}
+
+/// Splits a container type string like `Vec<u32>` into its outer container
+/// name (`Vec`) and inner element type (`u32`), if `type_name` has that
+/// shape. A field type with no `<...>` (including a bare `Vec`/`Option`
+/// with no element type given) isn't a container.
+fn container_parts(type_name: &str) -> Option<(&str, &str)> {
+    let open = type_name.find('<')?;
+    if !type_name.ends_with('>') {
+        return None;
+    }
+    let outer = &type_name[..open];
+    let inner = &type_name[open + 1..type_name.len() - 1];
+    if outer.is_empty() || inner.is_empty() {
+        return None;
+    }
+    Some((outer, inner))
+}
+
+/// The workspace struct a field type references, if any: the type itself
+/// when it's a bare non-primitive name, or its element type when it's a
+/// primitive container (`Vec<Foo>`, `Option<Foo>`) wrapping one, recursing
+/// through nested containers. Used to build each generator's
+/// import/include/`$ref` list without mistaking `Vec<u32>` for a reference
+/// to a workspace struct literally named `Vec<u32>`.
+fn referenced_struct(type_name: &str) -> Option<&str> {
+    if let Some((outer, inner)) = container_parts(type_name) {
+        return if is_primitive(outer) {
+            referenced_struct(inner)
+        } else {
+            None
+        };
+    }
+    if is_primitive(type_name) {
+        None
+    } else {
+        Some(type_name)
+    }
+}
+
+struct RustGenerator;
+
+impl CodeGenerator for RustGenerator {
+    fn generate(&self, model: &StructModel) -> String {
+        let mut imports: Vec<&str> = model
+            .fields
+            .iter()
+            .filter_map(|f| referenced_struct(&f.field_type))
+            .collect();
+        imports.sort();
+        imports.dedup();
+
+        let mut out = String::new();
+        for name in &imports {
+            out.push_str(&format!("use super::{}::{};\n", name.to_lowercase(), name));
+        }
+        if !imports.is_empty() {
+            out.push('\n');
+        }
+
+        out.push_str(&format!("pub struct {} {{\n", model.name));
+        for field in &model.fields {
+            if let Some(doc) = &field.doc {
+                out.push_str(&format!("    /// {}\n", doc));
+            }
+            let ty = rust_type(field);
+            out.push_str(&format!("    pub {}: {},\n", field.name, ty));
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+fn rust_type(field: &FieldDef) -> String {
+    let base = rust_type_name(&field.field_type);
+    if field.nullable {
+        format!("Option<{}>", base)
+    } else {
+        base
+    }
+}
+
+/// Renders a field type string as Rust, recursing into container types
+/// (`Vec<Foo>` -> `Vec<Foo>`, unchanged) and substituting a concrete
+/// element type for a bare `Vec`/`Option` with none given, since neither
+/// parses as a type on its own.
+fn rust_type_name(type_name: &str) -> String {
+    if let Some((outer, inner)) = container_parts(type_name) {
+        return format!("{}<{}>", outer, rust_type_name(inner));
+    }
+    match type_name {
+        "Vec" => "Vec<serde_json::Value>".to_string(),
+        "Option" => "Option<serde_json::Value>".to_string(),
+        other => other.to_string(),
+    }
+}
+
+struct CGenerator;
+
+impl CodeGenerator for CGenerator {
+    fn generate(&self, model: &StructModel) -> String {
+        let mut includes: Vec<&str> = model
+            .fields
+            .iter()
+            .filter_map(|f| referenced_struct(&f.field_type))
+            .collect();
+        includes.sort();
+        includes.dedup();
+
+        let mut out = String::new();
+        for name in &includes {
+            out.push_str(&format!("#include \"{}.h\"\n", name.to_lowercase()));
+        }
+        if !includes.is_empty() {
+            out.push('\n');
+        }
+
+        out.push_str(&format!("typedef struct {} {{\n", model.name));
+        for field in &model.fields {
+            if let Some(doc) = &field.doc {
+                out.push_str(&format!("    // {}\n", doc));
+            }
+            let ty = c_type(field);
+            out.push_str(&format!("    {} {};\n", ty, field.name));
+        }
+        out.push_str(&format!("}} {};\n", model.name));
+        out
+    }
+}
+
+fn c_type(field: &FieldDef) -> String {
+    let base = c_type_name(&field.field_type);
+    // Nullable fields (scalars and structs alike) are represented as
+    // pointers; a type that is already a pointer (`char*`, a container,
+    // `void*`) is left as is rather than wrapped again.
+    if field.nullable && !base.ends_with('*') {
+        format!("{}*", base)
+    } else {
+        base
+    }
+}
+
+/// Renders a field type string as C. `Vec<T>`/`Option<T>` have no C
+/// generic equivalent, so both are represented as a pointer to the
+/// element type (C has no way to express "dynamically sized" beyond a
+/// pointer, and that's already this codegen's convention for optional
+/// scalars); a bare `Vec`/`Option` with no element type falls back to
+/// `void*`.
+fn c_type_name(type_name: &str) -> String {
+    if let Some((outer, inner)) = container_parts(type_name) {
+        let inner_ty = c_type_name(inner);
+        return match outer {
+            "Vec" | "Option" => format!("{}*", inner_ty),
+            other => format!("struct {}", other),
+        };
+    }
+    match type_name {
+        "bool" => "bool".to_string(),
+        "u8" => "uint8_t".to_string(),
+        "u16" => "uint16_t".to_string(),
+        "u32" => "uint32_t".to_string(),
+        "u64" => "uint64_t".to_string(),
+        "i8" => "int8_t".to_string(),
+        "i16" => "int16_t".to_string(),
+        "i32" => "int32_t".to_string(),
+        "i64" => "int64_t".to_string(),
+        "f32" => "float".to_string(),
+        "f64" => "double".to_string(),
+        "String" => "char*".to_string(),
+        "Vec" | "Option" => "void*".to_string(),
+        other => format!("struct {}", other),
+    }
+}
+
+struct TypeScriptGenerator;
+
+impl CodeGenerator for TypeScriptGenerator {
+    fn generate(&self, model: &StructModel) -> String {
+        let mut imports: Vec<&str> = model
+            .fields
+            .iter()
+            .filter_map(|f| referenced_struct(&f.field_type))
+            .collect();
+        imports.sort();
+        imports.dedup();
+
+        let mut out = String::new();
+        for name in &imports {
+            out.push_str(&format!("import {{ {} }} from './{}';\n", name, name));
+        }
+        if !imports.is_empty() {
+            out.push('\n');
+        }
+
+        out.push_str(&format!("export interface {} {{\n", model.name));
+        for field in &model.fields {
+            if let Some(doc) = &field.doc {
+                out.push_str(&format!("  /** {} */\n", doc));
+            }
+            let ty = ts_type(&field.field_type);
+            let optional = if field.nullable { "?" } else { "" };
+            out.push_str(&format!("  {}{}: {};\n", field.name, optional, ty));
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+fn ts_type(field_type: &str) -> String {
+    if let Some((outer, inner)) = container_parts(field_type) {
+        let inner_ty = ts_type(inner);
+        return match outer {
+            "Vec" => format!("{}[]", inner_ty),
+            // `nullable` already drives the `?` marker on the property, so
+            // `Option<T>` as a bare type just aliases to its element type.
+            "Option" => inner_ty,
+            other => format!("{}<{}>", other, inner_ty),
+        };
+    }
+    match field_type {
+        "bool" => "boolean".to_string(),
+        "u8" | "u16" | "u32" | "u64" | "i8" | "i16" | "i32" | "i64" | "f32" | "f64" => {
+            "number".to_string()
+        }
+        "String" => "string".to_string(),
+        "Vec" => "unknown[]".to_string(),
+        "Option" => "unknown".to_string(),
+        other => other.to_string(),
+    }
+}
+
+struct JsonSchemaGenerator;
+
+impl CodeGenerator for JsonSchemaGenerator {
+    fn generate(&self, model: &StructModel) -> String {
+        let properties: serde_json::Map<String, serde_json::Value> = model
+            .fields
+            .iter()
+            .map(|field| (field.name.clone(), json_schema_for_type(&field.field_type)))
+            .collect();
+
+        let required: Vec<&str> = model
+            .fields
+            .iter()
+            .filter(|f| !f.nullable)
+            .map(|f| f.name.as_str())
+            .collect();
+
+        let schema = serde_json::json!({
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "title": model.name,
+            "type": "object",
+            "properties": properties,
+            "required": required,
+        });
+
+        serde_json::to_string_pretty(&schema).unwrap_or_default()
+    }
+}
+
+/// Renders a field type string as a JSON Schema value schema, recursing
+/// into containers so `Vec<Foo>` becomes an `array` of `Foo` refs rather
+/// than a `$ref` to a workspace struct literally named `Vec<Foo>`, and
+/// `Option<Foo>` schemas as plain `Foo` (optionality is already carried by
+/// `required`).
+fn json_schema_for_type(type_name: &str) -> serde_json::Value {
+    if let Some((outer, inner)) = container_parts(type_name) {
+        return match outer {
+            "Vec" => serde_json::json!({ "type": "array", "items": json_schema_for_type(inner) }),
+            "Option" => json_schema_for_type(inner),
+            _ => serde_json::json!({ "$ref": format!("{}.json", type_name) }),
+        };
+    }
+    if is_primitive(type_name) {
+        serde_json::json!({ "type": json_schema_type(type_name) })
+    } else {
+        serde_json::json!({ "$ref": format!("{}.json", type_name) })
+    }
+}
+
+fn json_schema_type(field_type: &str) -> &'static str {
+    match field_type {
+        "bool" => "boolean",
+        "u8" | "u16" | "u32" | "u64" | "i8" | "i16" | "i32" | "i64" => "integer",
+        "f32" | "f64" => "number",
+        "String" => "string",
+        "Vec" => "array",
+        _ => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(name: &str, field_type: &str, nullable: bool) -> FieldDef {
+        FieldDef {
+            name: name.to_string(),
+            field_type: field_type.to_string(),
+            nullable,
+            default: None,
+            doc: None,
+        }
+    }
+
+    fn model(fields: Vec<FieldDef>) -> StructModel {
+        StructModel {
+            name: "Widget".to_string(),
+            fields,
+        }
+    }
+
+    #[test]
+    fn referenced_struct_finds_container_element() {
+        assert_eq!(referenced_struct("Vec<Foo>"), Some("Foo"));
+        assert_eq!(referenced_struct("Option<Foo>"), Some("Foo"));
+        assert_eq!(referenced_struct("Foo"), Some("Foo"));
+        assert_eq!(referenced_struct("u32"), None);
+        assert_eq!(referenced_struct("Vec<u32>"), None);
+    }
+
+    #[test]
+    fn referenced_struct_recurses_through_nested_containers() {
+        assert_eq!(referenced_struct("Vec<Option<Foo>>"), Some("Foo"));
+        assert_eq!(referenced_struct("Option<Vec<u32>>"), None);
+    }
+
+    #[test]
+    fn rust_type_name_handles_containers_and_bare_containers() {
+        assert_eq!(rust_type_name("Vec<Foo>"), "Vec<Foo>");
+        assert_eq!(rust_type_name("Vec<u32>"), "Vec<u32>");
+        assert_eq!(rust_type_name("Vec"), "Vec<serde_json::Value>");
+        assert_eq!(rust_type_name("Option"), "Option<serde_json::Value>");
+        assert_eq!(rust_type_name("Vec<Option<Foo>>"), "Vec<Option<Foo>>");
+    }
+
+    #[test]
+    fn rust_generator_imports_referenced_structs_and_wraps_nullable_fields() {
+        let m = model(vec![
+            field("id", "u32", false),
+            field("owner", "Person", true),
+            field("tags", "Vec<Tag>", false),
+        ]);
+        let out = RustGenerator.generate(&m);
+        assert!(out.contains("use super::person::Person;"));
+        assert!(out.contains("use super::tag::Tag;"));
+        assert!(out.contains("pub owner: Option<Person>,"));
+        assert!(out.contains("pub tags: Vec<Tag>,"));
+    }
+
+    #[test]
+    fn c_type_name_handles_containers_and_bare_containers() {
+        assert_eq!(c_type_name("Vec<u32>"), "uint32_t*");
+        assert_eq!(c_type_name("Option<u32>"), "uint32_t*");
+        assert_eq!(c_type_name("Vec<Foo>"), "struct Foo*");
+        assert_eq!(c_type_name("Vec"), "void*");
+        assert_eq!(c_type_name("Option"), "void*");
+        assert_eq!(c_type_name("Foo"), "struct Foo");
+    }
+
+    #[test]
+    fn c_generator_includes_referenced_structs() {
+        let m = model(vec![field("owner", "Person", false)]);
+        let out = CGenerator.generate(&m);
+        assert!(out.contains("#include \"person.h\""));
+        assert!(out.contains("struct Person owner;"));
+    }
+
+    #[test]
+    fn ts_type_handles_containers_and_bare_containers() {
+        assert_eq!(ts_type("Vec<Foo>"), "Foo[]");
+        assert_eq!(ts_type("Option<Foo>"), "Foo");
+        assert_eq!(ts_type("Vec<Vec<u32>>"), "number[][]");
+        assert_eq!(ts_type("Vec"), "unknown[]");
+        assert_eq!(ts_type("Option"), "unknown");
+    }
+
+    #[test]
+    fn typescript_generator_imports_referenced_structs() {
+        let m = model(vec![field("owner", "Person", true)]);
+        let out = TypeScriptGenerator.generate(&m);
+        assert!(out.contains("import { Person } from './Person';"));
+        assert!(out.contains("owner?: Person;"));
+    }
+
+    #[test]
+    fn json_schema_for_type_handles_containers_and_nullable_structs() {
+        assert_eq!(
+            json_schema_for_type("Vec<Foo>"),
+            serde_json::json!({ "type": "array", "items": { "$ref": "Foo.json" } })
+        );
+        assert_eq!(json_schema_for_type("Option<Foo>"), serde_json::json!({ "$ref": "Foo.json" }));
+        assert_eq!(json_schema_for_type("Foo"), serde_json::json!({ "$ref": "Foo.json" }));
+        assert_eq!(json_schema_for_type("u32"), serde_json::json!({ "type": "integer" }));
+    }
+
+    #[test]
+    fn json_schema_generator_omits_nullable_fields_from_required() {
+        let m = model(vec![
+            field("id", "u32", false),
+            field("owner", "Person", true),
+        ]);
+        let out = JsonSchemaGenerator.generate(&m);
+        let schema: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(schema["required"], serde_json::json!(["id"]));
+        assert_eq!(schema["properties"]["owner"], serde_json::json!({ "$ref": "Person.json" }));
+    }
+}
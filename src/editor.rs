@@ -0,0 +1,329 @@
+//! The multi-panel struct editor.
+//!
+//! [`StructEditor`] owns the in-memory [`StructModel`] for a single `.struct`
+//! definition and hosts the three workspace panels (properties, fields, code
+//! preview) that make up the editor's UI.
+
+use gpui::*;
+use plugin_editor_api::PluginError;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::process::ExitStatus;
+use std::time::Duration;
+
+use crate::manifest::StructEditorManifest;
+use crate::watcher::FileWatcher;
+use crate::workspace_panels::{
+    CodePreviewPanel, FieldsPanel, FieldsPanelEvent, ProcessHandle, ProcessId, PropertiesPanel,
+    SpawnRequest,
+};
+
+/// How often the editor polls its [`FileWatcher`] for external changes.
+const WATCH_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Events emitted by [`StructEditor`] when `struct.json` changes on disk.
+#[derive(Debug, Clone)]
+pub enum StructEditorEvent {
+    /// The file changed externally while the editor had no unsaved edits;
+    /// it was reloaded automatically.
+    ReloadedFromDisk,
+    /// The file changed externally while the editor had unsaved edits; the
+    /// in-memory model was left untouched and the host should prompt the
+    /// user to reconcile (keep mine / take theirs / diff) rather than
+    /// silently clobbering either side.
+    ReconcileNeeded,
+}
+
+/// A single field in a struct definition, as stored in `struct.json`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FieldDef {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub field_type: String,
+    #[serde(default)]
+    pub nullable: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc: Option<String>,
+}
+
+/// The in-memory representation of a `struct.json` definition.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StructModel {
+    pub name: String,
+    #[serde(default)]
+    pub fields: Vec<FieldDef>,
+}
+
+/// Multi-panel editor for a single `.struct` definition.
+pub struct StructEditor {
+    file_path: PathBuf,
+    model: StructModel,
+    /// Canonical serialization of the model as last read from or written to
+    /// disk; `is_dirty` compares the live model against this.
+    last_saved_serialized: String,
+    watcher: Option<FileWatcher>,
+    properties_panel: Entity<PropertiesPanel>,
+    fields_panel: Entity<FieldsPanel>,
+    code_preview_panel: Entity<CodePreviewPanel>,
+    focus_handle: FocusHandle,
+}
+
+impl StructEditor {
+    pub fn new_with_file(
+        file_path: PathBuf,
+        manifest: StructEditorManifest,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let model = Self::load_model(&file_path).unwrap_or_default();
+        // `file_path` is `<workspace>/<name>.struct/struct.json`; the workspace
+        // root is what we scan for sibling `.struct` definitions.
+        let workspace_root = file_path
+            .parent()
+            .and_then(|struct_folder| struct_folder.parent())
+            .map(PathBuf::from)
+            .unwrap_or_default();
+
+        let properties_panel = cx.new(|cx| PropertiesPanel::new(model.clone(), window, cx));
+        let fields_panel = cx.new(|cx| {
+            FieldsPanel::new(
+                model.clone(),
+                workspace_root,
+                manifest.type_palette.clone(),
+                manifest.field_defaults.clone(),
+                window,
+                cx,
+            )
+        });
+        let code_preview_panel = cx.new(|cx| CodePreviewPanel::new(model.clone(), window, cx));
+        let last_saved_serialized = Self::serialize(&model);
+
+        // `FieldsPanel` is where field-mutating commands (add/remove/sort,
+        // whether palette- or command-palette-driven) actually apply
+        // `field_defaults` and rebuild field editors; it owns the logic but
+        // not the truth. Pull its model back into ours and out to the other
+        // panels whenever it changes, so `self.model` stays the single
+        // source of truth `plugin_save`/`is_dirty`/codegen read.
+        cx.subscribe(&fields_panel, |this, fields_panel, event, cx| {
+            let FieldsPanelEvent::ModelChanged = event;
+            this.model = fields_panel.read(cx).model().clone();
+            let model = this.model.clone();
+            this.properties_panel
+                .update(cx, |panel, cx| panel.set_model(model.clone(), cx));
+            this.code_preview_panel
+                .update(cx, |panel, cx| panel.set_model(model, cx));
+            cx.notify();
+        })
+        .detach();
+
+        let watcher = FileWatcher::new(&file_path).ok();
+
+        cx.spawn(async move |this, cx| loop {
+            cx.background_executor().timer(WATCH_POLL_INTERVAL).await;
+            let result = this.update(cx, |editor, cx| editor.check_for_external_changes(cx));
+            if result.is_err() {
+                break;
+            }
+        })
+        .detach();
+
+        Self {
+            file_path,
+            model,
+            last_saved_serialized,
+            watcher,
+            properties_panel,
+            fields_panel,
+            code_preview_panel,
+            focus_handle: cx.focus_handle(),
+        }
+    }
+
+    /// Reads and parses `struct.json`. A missing file and an unparseable one
+    /// are both returned as errors rather than as an empty model; only editor
+    /// creation (`new_with_file`) falls back to an empty model itself. Callers
+    /// that reload an *already-open* editor (`plugin_reload`,
+    /// `check_for_external_changes`) must not clobber live in-memory state
+    /// with `{"name":"","fields":[]}` just because the file was caught
+    /// mid-write.
+    fn load_model(path: &PathBuf) -> std::io::Result<StructModel> {
+        let contents = std::fs::read_to_string(path)?;
+        serde_json::from_str(&contents)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+
+    fn serialize(model: &StructModel) -> String {
+        serde_json::to_string_pretty(model).unwrap_or_default()
+    }
+
+    pub fn model(&self) -> &StructModel {
+        &self.model
+    }
+
+    /// Whether the in-memory model has edits not yet reflected on disk.
+    pub fn is_dirty(&self) -> bool {
+        Self::serialize(&self.model) != self.last_saved_serialized
+    }
+
+    pub fn plugin_save(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> Result<(), PluginError> {
+        let serialized = Self::serialize(&self.model);
+        std::fs::write(&self.file_path, &serialized)
+            .map_err(|e| PluginError::Other(e.to_string()))?;
+        self.last_saved_serialized = serialized;
+        cx.notify();
+        Ok(())
+    }
+
+    pub fn plugin_reload(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> Result<(), PluginError> {
+        // Propagate a read or parse failure instead of replacing the
+        // (possibly dirty) live model with an empty one.
+        let model = Self::load_model(&self.file_path).map_err(|e| PluginError::Other(e.to_string()))?;
+        self.model = model;
+        self.last_saved_serialized = Self::serialize(&self.model);
+        self.sync_model_to_panels(cx);
+        Ok(())
+    }
+
+    /// Polls the background watcher and reacts to external changes: a clean
+    /// editor reloads silently (mirroring the existing `plugin_reload`
+    /// path, which keeps every editor open on the same folder consistent);
+    /// a dirty editor instead emits [`StructEditorEvent::ReconcileNeeded`]
+    /// so the host can prompt rather than clobber unsaved edits.
+    fn check_for_external_changes(&mut self, cx: &mut Context<Self>) {
+        let Some(watcher) = &self.watcher else {
+            return;
+        };
+        if !watcher.poll_changed() {
+            return;
+        }
+
+        // `plugin_save` writes this same file, which the watcher reports
+        // back as an external modification; re-reading and comparing
+        // against `last_saved_serialized` (updated by `plugin_save` itself)
+        // tells apart that self-induced echo from a genuine external edit.
+        //
+        // A read that succeeds but fails to parse (the file caught
+        // mid-write by another process) also falls through here and is
+        // skipped rather than adopted: `load_model` surfaces a parse
+        // failure as `Err`, so it never reaches the comparison below as a
+        // spurious "empty model" change.
+        let Ok(on_disk) = Self::load_model(&self.file_path) else {
+            return;
+        };
+        if Self::serialize(&on_disk) == self.last_saved_serialized {
+            return;
+        }
+
+        if self.is_dirty() {
+            cx.emit(StructEditorEvent::ReconcileNeeded);
+            cx.notify();
+        } else {
+            self.model = on_disk;
+            self.last_saved_serialized = Self::serialize(&self.model);
+            self.sync_model_to_panels(cx);
+            cx.emit(StructEditorEvent::ReloadedFromDisk);
+        }
+    }
+
+    /// Pushes `self.model` (the source of truth) out to all three panels,
+    /// which otherwise each hold an independent clone taken at construction
+    /// or the last time they were synced.
+    fn sync_model_to_panels(&mut self, cx: &mut Context<Self>) {
+        let model = self.model.clone();
+        self.properties_panel
+            .update(cx, |panel, cx| panel.set_model(model.clone(), cx));
+        self.fields_panel
+            .update(cx, |panel, cx| panel.set_model(model.clone(), cx));
+        self.code_preview_panel
+            .update(cx, |panel, cx| panel.set_model(model, cx));
+        cx.notify();
+    }
+
+    /// Drains any external-tool runs the code preview panel has queued (e.g.
+    /// "format" or "validate"), for the host to actually spawn.
+    pub fn take_pending_spawns(&mut self, cx: &mut App) -> Vec<SpawnRequest> {
+        self.code_preview_panel
+            .update(cx, |panel, _cx| panel.take_pending_spawns())
+    }
+
+    pub fn on_process_spawned(&mut self, id: ProcessId, handle: ProcessHandle, cx: &mut App) {
+        self.code_preview_panel
+            .update(cx, |panel, cx| panel.on_process_spawned(id, handle, cx));
+    }
+
+    pub fn on_process_output(&mut self, id: ProcessId, data: &[u8], cx: &mut App) {
+        self.code_preview_panel
+            .update(cx, |panel, cx| panel.on_process_output(id, data, cx));
+    }
+
+    pub fn on_process_exit(&mut self, id: ProcessId, status: ExitStatus, cx: &mut App) {
+        self.code_preview_panel
+            .update(cx, |panel, cx| panel.on_process_exit(id, status, cx));
+    }
+
+    // The following mirror the command palette commands exposed by
+    // `StructEditorPlugin::commands`, so they can be invoked by id/args
+    // rather than only through direct panel clicks.
+
+    pub fn add_field(&mut self, name: String, field_type: String, cx: &mut App) {
+        let palette_entry = crate::manifest::TypePaletteEntry {
+            display_name: field_type.clone(),
+            icon: "type".to_string(),
+            type_string: field_type,
+        };
+        self.fields_panel
+            .update(cx, |panel, cx| panel.add_field(name, &palette_entry, cx));
+    }
+
+    pub fn remove_field(&mut self, name: &str, cx: &mut App) {
+        self.fields_panel
+            .update(cx, |panel, cx| panel.remove_field(name, cx));
+    }
+
+    /// Re-runs type completion for the field named `name` as its type text
+    /// is edited, applying `partial_text` as the field's in-progress type.
+    pub fn retype_field(&mut self, name: &str, partial_text: String, cursor_position: usize, cx: &mut App) {
+        self.fields_panel.update(cx, |panel, cx| {
+            panel.retype_field(name, partial_text, cursor_position, cx)
+        });
+    }
+
+    pub fn sort_fields(&mut self, cx: &mut App) {
+        self.fields_panel.update(cx, |panel, cx| panel.sort_fields(cx));
+    }
+
+    pub fn set_code_gen_target(&mut self, target: crate::codegen::CodeGenTarget, cx: &mut App) {
+        self.code_preview_panel
+            .update(cx, |panel, cx| panel.set_target(target, cx));
+    }
+
+    /// Queues a "validate" run against the current `struct.json` (see the
+    /// subprocess lifecycle hooks); the host performs the actual spawn.
+    pub fn request_validate(&mut self, cx: &mut App) {
+        let path = self.file_path.clone();
+        self.code_preview_panel.update(cx, |panel, cx| {
+            panel.request_run("validate", vec![path.display().to_string()], cx)
+        });
+    }
+}
+
+impl EventEmitter<StructEditorEvent> for StructEditor {}
+
+impl Render for StructEditor {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .flex()
+            .size_full()
+            .child(self.properties_panel.clone())
+            .child(self.fields_panel.clone())
+            .child(self.code_preview_panel.clone())
+    }
+}
+
+impl Focusable for StructEditor {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
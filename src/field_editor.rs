@@ -0,0 +1,228 @@
+//! The per-field editing widget used inside [`crate::workspace_panels::FieldsPanel`].
+
+use gpui::*;
+use std::path::PathBuf;
+
+use crate::editor::{FieldDef, StructModel};
+
+/// Events emitted by [`FieldEditorView`] as the user edits a field.
+#[derive(Debug, Clone)]
+pub enum FieldEditorEvent {
+    /// The field's name changed.
+    NameChanged(String),
+    /// The field's type changed.
+    TypeChanged(String),
+    /// The field's nullability was toggled.
+    NullableToggled(bool),
+    /// The user asked for this field to be removed.
+    RemoveRequested,
+}
+
+/// Primitive types always offered in type-name completions, ahead of any
+/// user-defined struct names.
+pub(crate) const PRIMITIVE_TYPES: &[&str] = &[
+    "bool", "u8", "u16", "u32", "u64", "i8", "i16", "i32", "i64", "f32", "f64", "String", "Vec",
+    "Option",
+];
+
+/// Distinguishes a built-in primitive from a user-defined struct in a
+/// [`TypeCompletionCandidate`] list, so the UI can icon them differently.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypeCompletionKind {
+    Primitive,
+    UserStruct,
+}
+
+/// One candidate offered while completing a field's type name.
+#[derive(Debug, Clone)]
+pub struct TypeCompletionCandidate {
+    pub name: String,
+    pub kind: TypeCompletionKind,
+}
+
+/// Request to complete a field's type name as the user types it.
+#[derive(Debug, Clone)]
+pub struct TypeCompletionContext {
+    /// The partial text typed so far.
+    pub partial_text: String,
+    /// Cursor position within `partial_text`.
+    pub cursor_position: usize,
+    /// Index of the field being edited, within the parent struct's fields.
+    pub field_index: usize,
+}
+
+/// Editable view over a single [`FieldDef`].
+pub struct FieldEditorView {
+    field_index: usize,
+    field: FieldDef,
+    /// Directory scanned for sibling `.struct` definitions when completing
+    /// type names (the workspace folder containing the `.struct` folders).
+    workspace_root: PathBuf,
+    /// Name of the struct this field belongs to, excluded from its own
+    /// type-name completions (a struct can't reference itself as a field).
+    struct_name: String,
+    /// Candidates from the most recent [`Self::edit_type`] call, rendered
+    /// below the field as the user types.
+    type_completions: Vec<TypeCompletionCandidate>,
+    focus_handle: FocusHandle,
+}
+
+impl FieldEditorView {
+    pub fn new(
+        field_index: usize,
+        field: FieldDef,
+        workspace_root: PathBuf,
+        struct_name: String,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        Self {
+            field_index,
+            field,
+            workspace_root,
+            struct_name,
+            type_completions: Vec::new(),
+            focus_handle: cx.focus_handle(),
+        }
+    }
+
+    pub fn field_index(&self) -> usize {
+        self.field_index
+    }
+
+    pub fn field(&self) -> &FieldDef {
+        &self.field
+    }
+
+    /// The completion candidates produced by the most recent [`Self::edit_type`] call.
+    pub fn type_completions(&self) -> &[TypeCompletionCandidate] {
+        &self.type_completions
+    }
+
+    /// Applies a type-name edit as the user types it: re-runs [`Self::complete_type`]
+    /// for the new text and stores the resulting candidates for render to
+    /// surface, sets the field's type to `partial_text` (so the preview and
+    /// saved model reflect the in-progress edit, same as every other field
+    /// edit in this plugin), and emits [`FieldEditorEvent::TypeChanged`] so
+    /// [`crate::workspace_panels::FieldsPanel`] can fold the edit back into
+    /// its model.
+    pub fn edit_type(&mut self, partial_text: String, cursor_position: usize, cx: &mut Context<Self>) {
+        let context = TypeCompletionContext {
+            partial_text: partial_text.clone(),
+            cursor_position,
+            field_index: self.field_index,
+        };
+        let (candidates, _found_any) = self.complete_type(&context);
+        self.type_completions = candidates;
+        self.field.field_type = partial_text.clone();
+        cx.emit(FieldEditorEvent::TypeChanged(partial_text));
+        cx.notify();
+    }
+
+    /// Resolves type-name completions for the current edit: primitives plus
+    /// every *other* `.struct` definition discoverable in the workspace.
+    /// Returns whether any completion was produced, so the editor can fall
+    /// back to free-text entry when nothing matches.
+    pub fn complete_type(&self, context: &TypeCompletionContext) -> (Vec<TypeCompletionCandidate>, bool) {
+        // A completion request is scoped to the field it was raised for;
+        // ignore one raised for a different field than this view owns.
+        if context.field_index != self.field_index {
+            return (Vec::new(), false);
+        }
+
+        let prefix = Self::prefix_before_cursor(&context.partial_text, context.cursor_position);
+        let partial = prefix.to_lowercase();
+        let mut candidates: Vec<TypeCompletionCandidate> = PRIMITIVE_TYPES
+            .iter()
+            .filter(|name| partial.is_empty() || name.to_lowercase().starts_with(&partial))
+            .map(|name| TypeCompletionCandidate {
+                name: name.to_string(),
+                kind: TypeCompletionKind::Primitive,
+            })
+            .collect();
+
+        for name in Self::discover_workspace_struct_names(&self.workspace_root, &self.struct_name) {
+            if partial.is_empty() || name.to_lowercase().starts_with(&partial) {
+                candidates.push(TypeCompletionCandidate {
+                    name,
+                    kind: TypeCompletionKind::UserStruct,
+                });
+            }
+        }
+
+        let found_any = !candidates.is_empty();
+        (candidates, found_any)
+    }
+
+    /// The portion of `text` up to `cursor_position` (a char, not byte,
+    /// offset), so completion matches what's actually typed before the
+    /// cursor rather than the whole field.
+    fn prefix_before_cursor(text: &str, cursor_position: usize) -> &str {
+        match text.char_indices().nth(cursor_position) {
+            Some((byte_index, _)) => &text[..byte_index],
+            None => text,
+        }
+    }
+
+    /// Scans `workspace_root` for `.struct` folders (identified by a
+    /// `struct.json` marker file) and returns the struct names they define,
+    /// excluding `exclude_name` (the struct being edited, which can't
+    /// reference itself as a field type).
+    fn discover_workspace_struct_names(workspace_root: &PathBuf, exclude_name: &str) -> Vec<String> {
+        let mut names = Vec::new();
+        let Ok(entries) = std::fs::read_dir(workspace_root) else {
+            return names;
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if !path.is_dir() {
+                continue;
+            }
+            let marker = path.join("struct.json");
+            let Ok(contents) = std::fs::read_to_string(&marker) else {
+                continue;
+            };
+            if let Ok(model) = serde_json::from_str::<StructModel>(&contents) {
+                if model.name != exclude_name {
+                    names.push(model.name);
+                }
+            }
+        }
+
+        names.sort();
+        names.dedup();
+        names
+    }
+}
+
+impl EventEmitter<FieldEditorEvent> for FieldEditorView {}
+
+impl Focusable for FieldEditorView {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for FieldEditorView {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .flex()
+            .flex_col()
+            .child(
+                div()
+                    .flex()
+                    .gap_2()
+                    .child(self.field.name.clone())
+                    .child(self.field.field_type.clone()),
+            )
+            .children(if self.type_completions.is_empty() {
+                None
+            } else {
+                Some(div().flex().flex_col().children(
+                    self.type_completions
+                        .iter()
+                        .map(|candidate| candidate.name.clone()),
+                ))
+            })
+    }
+}
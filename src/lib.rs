@@ -15,7 +15,7 @@
 
 use plugin_editor_api::*;
 use serde_json::json;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::Mutex;
 use std::collections::HashMap;
@@ -23,14 +23,22 @@ use gpui::*;
 use ui::dock::PanelView;
 
 // Struct Editor modules
+mod codegen;
 mod editor;
 mod field_editor;
+mod manifest;
+mod watcher;
 mod workspace_panels;
 
 // Re-export main types
-pub use editor::StructEditor;
-pub use field_editor::{FieldEditorView, FieldEditorEvent};
-pub use workspace_panels::{PropertiesPanel, FieldsPanel, CodePreviewPanel};
+pub use codegen::CodeGenTarget;
+pub use editor::{StructEditor, StructEditorEvent};
+pub use field_editor::{
+    FieldEditorView, FieldEditorEvent, TypeCompletionCandidate, TypeCompletionContext,
+    TypeCompletionKind,
+};
+pub use manifest::{FieldDefaults, StructEditorManifest, StructTemplate, TypePaletteEntry};
+pub use workspace_panels::{PropertiesPanel, FieldsPanel, FieldsPanelEvent, CodePreviewPanel, ProcessHandle, ProcessId, SpawnRequest};
 
 /// Storage for editor instances owned by the plugin
 struct EditorStorage {
@@ -38,12 +46,32 @@ struct EditorStorage {
     wrapper: Box<StructEditorWrapper>,
 }
 
+/// A command the struct editor exposes to a host command palette or
+/// keybinding, dispatched through [`StructEditorPlugin::execute`].
+#[derive(Debug, Clone)]
+pub struct PluginCommand {
+    pub id: String,
+    pub name: String,
+    pub help: String,
+}
+
 /// The Struct Editor Plugin
 pub struct StructEditorPlugin {
     /// CRITICAL: Plugin owns ALL editor instances to prevent memory leaks!
     /// The main app only gets raw pointers - it NEVER owns the Arc or Box.
     editors: Arc<Mutex<HashMap<usize, EditorStorage>>>,
     next_editor_id: Arc<Mutex<usize>>,
+    /// Manifests already loaded, keyed by project root, so repeated editors
+    /// in the same project don't re-read and re-parse the file.
+    manifests: Arc<Mutex<HashMap<PathBuf, StructEditorManifest>>>,
+    /// Root of the most recently opened project, used by `file_types` to
+    /// surface that project's manifest-driven default content.
+    active_project_root: Arc<Mutex<Option<PathBuf>>>,
+    /// Id of the struct editor that last had focus (falling back to the
+    /// most recently opened one until focus moves), i.e. the one `execute`
+    /// acts on when the host invokes a command without targeting a specific
+    /// panel.
+    active_editor_id: Arc<Mutex<Option<usize>>>,
 }
 
 impl Default for StructEditorPlugin {
@@ -51,10 +79,136 @@ impl Default for StructEditorPlugin {
         Self {
             editors: Arc::new(Mutex::new(HashMap::new())),
             next_editor_id: Arc::new(Mutex::new(0)),
+            manifests: Arc::new(Mutex::new(HashMap::new())),
+            active_project_root: Arc::new(Mutex::new(None)),
+            active_editor_id: Arc::new(Mutex::new(None)),
         }
     }
 }
 
+impl StructEditorPlugin {
+    /// Returns the manifest for `project_root`, loading and caching it the
+    /// first time this project is seen.
+    fn manifest_for(&self, project_root: &Path) -> StructEditorManifest {
+        let mut manifests = self.manifests.lock().unwrap();
+        manifests
+            .entry(project_root.to_path_buf())
+            .or_insert_with(|| StructEditorManifest::load(project_root))
+            .clone()
+    }
+
+    /// The active project's starter templates, for a "new struct" UI to
+    /// offer a choice. Falls back to the built-in template if no project
+    /// has been opened yet.
+    pub fn available_templates(&self) -> Vec<StructTemplate> {
+        let project_root = self.active_project_root.lock().unwrap().clone();
+        match project_root {
+            Some(root) => self.manifest_for(&root).templates,
+            None => StructEditorManifest::builtin().templates,
+        }
+    }
+
+    /// Commands this plugin exposes to a host command palette or
+    /// keybinding, dispatched by id through [`Self::execute`].
+    pub fn commands(&self) -> Vec<PluginCommand> {
+        vec![
+            PluginCommand {
+                id: "add-field".into(),
+                name: "Add Field".into(),
+                help: "Add a field to the struct (args: name:type, e.g. `count:u32`)".into(),
+            },
+            PluginCommand {
+                id: "remove-field".into(),
+                name: "Remove Field".into(),
+                help: "Remove a field from the struct by name (args: name)".into(),
+            },
+            PluginCommand {
+                id: "retype-field".into(),
+                name: "Retype Field".into(),
+                help: "Re-run type completion for a field as its type is edited (args: name:partial_type)".into(),
+            },
+            PluginCommand {
+                id: "sort-fields".into(),
+                name: "Sort Fields".into(),
+                help: "Sort the struct's fields alphabetically by name".into(),
+            },
+            PluginCommand {
+                id: "generate-code".into(),
+                name: "Generate Code".into(),
+                help: "Switch the code preview target (args: rust|c|typescript|json-schema)".into(),
+            },
+            PluginCommand {
+                id: "validate".into(),
+                name: "Validate".into(),
+                help: "Run the configured validator against the current struct.json".into(),
+            },
+        ]
+    }
+
+    /// Routes a command palette invocation into the active struct editor.
+    pub fn execute(
+        &self,
+        command_id: &str,
+        args: &str,
+        _window: &mut Window,
+        cx: &mut App,
+    ) -> Result<(), PluginError> {
+        let editor_id = self
+            .active_editor_id
+            .lock()
+            .unwrap()
+            .ok_or_else(|| PluginError::Other("no active struct editor".to_string()))?;
+        let panel = {
+            let editors = self.editors.lock().unwrap();
+            let storage = editors
+                .get(&editor_id)
+                .ok_or_else(|| PluginError::Other("struct editor instance not found".to_string()))?;
+            storage.wrapper.panel.clone()
+        };
+
+        match command_id {
+            "add-field" => {
+                let (name, field_type) = args
+                    .split_once(':')
+                    .map(|(name, ty)| (name.trim().to_string(), ty.trim().to_string()))
+                    .ok_or_else(|| PluginError::Other("add-field expects `name:type`".to_string()))?;
+                panel.update(cx, |editor, cx| editor.add_field(name, field_type, cx));
+            }
+            "remove-field" => {
+                panel.update(cx, |editor, cx| editor.remove_field(args.trim(), cx));
+            }
+            "retype-field" => {
+                let (name, partial) = args
+                    .split_once(':')
+                    .map(|(name, ty)| (name.trim().to_string(), ty.trim().to_string()))
+                    .ok_or_else(|| PluginError::Other("retype-field expects `name:partial_type`".to_string()))?;
+                let cursor_position = partial.chars().count();
+                panel.update(cx, |editor, cx| editor.retype_field(&name, partial, cursor_position, cx));
+            }
+            "sort-fields" => {
+                panel.update(cx, |editor, cx| editor.sort_fields(cx));
+            }
+            "generate-code" => {
+                let target: CodeGenTarget = args
+                    .trim()
+                    .parse()
+                    .map_err(PluginError::Other)?;
+                panel.update(cx, |editor, cx| editor.set_code_gen_target(target, cx));
+            }
+            "validate" => {
+                panel.update(cx, |editor, cx| editor.request_validate(cx));
+            }
+            other => {
+                return Err(PluginError::Other(format!(
+                    "unknown struct-editor command: {other}"
+                )))
+            }
+        }
+
+        Ok(())
+    }
+}
+
 impl EditorPlugin for StructEditorPlugin {
     fn metadata(&self) -> PluginMetadata {
         PluginMetadata {
@@ -67,6 +221,12 @@ impl EditorPlugin for StructEditorPlugin {
     }
 
     fn file_types(&self) -> Vec<FileTypeDefinition> {
+        let manifest = match self.active_project_root.lock().unwrap().clone() {
+            Some(root) => self.manifest_for(&root),
+            None => StructEditorManifest::builtin(),
+        };
+        let default_template = manifest.default_template();
+
         vec![
             FileTypeDefinition {
                 id: FileTypeId::new("struct"),
@@ -80,7 +240,7 @@ impl EditorPlugin for StructEditorPlugin {
                 },
                 default_content: json!({
                     "name": "NewStruct",
-                    "fields": []
+                    "fields": default_template.fields,
                 }),
                 categories: vec!["Types".to_string()],
             }
@@ -114,11 +274,56 @@ impl EditorPlugin for StructEditorPlugin {
                 file_path.clone()
             };
 
+            // The `.struct` folder's parent is the project root the manifest
+            // lives in (`<project_root>/.pulsar/struct-editor.json`).
+            let project_root = actual_path
+                .parent()
+                .and_then(|struct_folder| struct_folder.parent())
+                .map(PathBuf::from)
+                .unwrap_or_default();
+            let manifest = self.manifest_for(&project_root);
+            *self.active_project_root.lock().unwrap() = Some(project_root);
+
             // Create a view context for the panel
             let panel = cx.new(|cx| {
-                StructEditor::new_with_file(actual_path.clone(), window, cx)
+                StructEditor::new_with_file(actual_path.clone(), manifest, window, cx)
             });
 
+            // Generate unique ID for this editor
+            let id = {
+                let mut next_id = self.next_editor_id.lock().unwrap();
+                let id = *next_id;
+                *next_id += 1;
+                id
+            };
+
+            // `is_dirty` on `EditorInstance` takes no context, so we can't read
+            // the entity from inside it; mirror its dirty state into a shared
+            // flag instead, kept current by observing the entity.
+            let dirty = Arc::new(std::sync::atomic::AtomicBool::new(false));
+            {
+                let dirty = dirty.clone();
+                cx.observe(&panel, move |panel, cx| {
+                    dirty.store(panel.read(cx).is_dirty(), std::sync::atomic::Ordering::Relaxed);
+                })
+                .detach();
+            }
+
+            // `execute` dispatches palette commands to whichever struct editor
+            // is "active"; with more than one `.struct` editor open, that has
+            // to be the one the user is actually focused on, not just the one
+            // created last. Track focus transitions into this editor's panel
+            // and flip `active_editor_id` whenever they happen.
+            {
+                let active_editor_id = self.active_editor_id.clone();
+                let focus_handle = panel.read(cx).focus_handle(cx);
+                window
+                    .on_focus_in(&focus_handle, cx, move |_window, _cx| {
+                        *active_editor_id.lock().unwrap() = Some(id);
+                    })
+                    .detach();
+            }
+
             // Wrap the panel in Arc - will be shared with main app
             let panel_arc: Arc<dyn ui::dock::PanelView> = Arc::new(panel.clone());
 
@@ -129,22 +334,20 @@ impl EditorPlugin for StructEditorPlugin {
             let wrapper = Box::new(StructEditorWrapper {
                 panel: panel.into(),
                 file_path,
+                dirty,
             });
 
-            // Generate unique ID for this editor
-            let id = {
-                let mut next_id = self.next_editor_id.lock().unwrap();
-                let id = *next_id;
-                *next_id += 1;
-                id
-            };
-
             // CRITICAL: Store Arc and Box in plugin's HashMap to keep them alive!
             self.editors.lock().unwrap().insert(id, EditorStorage {
                 panel: panel_arc.clone(),
                 wrapper: wrapper.clone(),
             });
 
+            // Newly created editors become active immediately (matching the
+            // prior creation-time behavior); focus transitions afterward are
+            // what keep this current across multiple open editors.
+            *self.active_editor_id.lock().unwrap() = Some(id);
+
             log::info!("Created struct editor instance {} for {:?}", id, file_path_for_log);
 
             // Return Arc (main app will clone it) and Box for EditorInstance
@@ -155,6 +358,9 @@ impl EditorPlugin for StructEditorPlugin {
     }
 
     fn on_load(&mut self) {
+        // Drop any cached manifests so the next editor opened re-reads
+        // `.pulsar/struct-editor.json` instead of serving a stale config.
+        self.manifests.lock().unwrap().clear();
         log::info!("Struct Editor Plugin loaded");
     }
 
@@ -163,6 +369,7 @@ impl EditorPlugin for StructEditorPlugin {
         let mut editors = self.editors.lock().unwrap();
         let count = editors.len();
         editors.clear();
+        *self.active_editor_id.lock().unwrap() = None;
         log::info!("Struct Editor Plugin unloaded (cleaned up {} editors)", count);
     }
 }
@@ -172,6 +379,10 @@ impl EditorPlugin for StructEditorPlugin {
 pub struct StructEditorWrapper {
     panel: Entity<StructEditor>,
     file_path: std::path::PathBuf,
+    /// Mirrors `StructEditor::is_dirty`, kept current by an observer set up
+    /// in `create_editor` (see there for why `is_dirty` can't just read the
+    /// entity directly).
+    dirty: Arc<std::sync::atomic::AtomicBool>,
 }
 
 impl plugin_editor_api::EditorInstance for StructEditorWrapper {
@@ -192,12 +403,38 @@ impl plugin_editor_api::EditorInstance for StructEditorWrapper {
     }
 
     fn is_dirty(&self) -> bool {
-        false
+        self.dirty.load(std::sync::atomic::Ordering::Relaxed)
     }
 
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
+
+    // Process-lifecycle hooks (default no-ops on `EditorInstance`): let the
+    // struct editor shell out to external validators/codegen/formatters over
+    // the current `struct.json` and stream results back into the
+    // `CodePreviewPanel` without blocking the UI. The plugin never spawns
+    // processes itself; the host drains `take_pending_spawns` and reports
+    // back through the other three hooks.
+
+    fn take_pending_spawns(&mut self, cx: &mut App) -> Vec<workspace_panels::SpawnRequest> {
+        self.panel.update(cx, |panel, cx| panel.take_pending_spawns(cx))
+    }
+
+    fn on_process_spawned(&mut self, id: u32, handle: workspace_panels::ProcessHandle, cx: &mut App) {
+        self.panel
+            .update(cx, |panel, cx| panel.on_process_spawned(id, handle, cx));
+    }
+
+    fn on_process_output(&mut self, id: u32, data: &[u8], cx: &mut App) {
+        self.panel
+            .update(cx, |panel, cx| panel.on_process_output(id, data, cx));
+    }
+
+    fn on_process_exit(&mut self, id: u32, status: std::process::ExitStatus, cx: &mut App) {
+        self.panel
+            .update(cx, |panel, cx| panel.on_process_exit(id, status, cx));
+    }
 }
 
 // Export the plugin using the provided macro
@@ -0,0 +1,110 @@
+//! Project-level configuration for the struct editor.
+//!
+//! A project can drop a `.pulsar/struct-editor.json` manifest in its root to
+//! customize the editor without recompiling the plugin: a palette of allowed
+//! field types, one or more named starter templates, and defaults applied to
+//! newly added fields. Anything the manifest omits falls back to the
+//! built-in defaults.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::Path;
+
+use crate::editor::FieldDef;
+use crate::field_editor::PRIMITIVE_TYPES;
+
+/// One entry in the configurable type palette offered when editing a
+/// field's type.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TypePaletteEntry {
+    pub display_name: String,
+    pub icon: String,
+    /// The literal string emitted into `struct.json` for this type.
+    pub type_string: String,
+}
+
+/// A named starter template selectable when creating a new `.struct`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StructTemplate {
+    pub name: String,
+    #[serde(default)]
+    pub fields: Vec<FieldDef>,
+}
+
+/// Defaults applied to a field when it is added through the palette, unless
+/// overridden by the user.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct FieldDefaults {
+    #[serde(default)]
+    pub nullable: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc: Option<String>,
+}
+
+/// Project-level struct editor configuration, merged with built-in defaults.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct StructEditorManifest {
+    #[serde(default)]
+    pub type_palette: Vec<TypePaletteEntry>,
+    #[serde(default)]
+    pub templates: Vec<StructTemplate>,
+    #[serde(default)]
+    pub field_defaults: FieldDefaults,
+}
+
+impl StructEditorManifest {
+    /// Relative to a project root: `<project_root>/.pulsar/struct-editor.json`.
+    pub const MANIFEST_PATH: &'static str = ".pulsar/struct-editor.json";
+
+    /// Loads the manifest for `project_root`, merging it with the built-in
+    /// palette/templates wherever the manifest leaves them empty. A missing
+    /// or unparsable manifest yields the built-in defaults untouched.
+    pub fn load(project_root: &Path) -> Self {
+        let contents = std::fs::read_to_string(project_root.join(Self::MANIFEST_PATH)).ok();
+        let manifest: Self = contents
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+        manifest.merged_with_builtin()
+    }
+
+    fn merged_with_builtin(mut self) -> Self {
+        if self.type_palette.is_empty() {
+            self.type_palette = Self::builtin_type_palette();
+        }
+        if self.templates.is_empty() {
+            self.templates = vec![Self::builtin_template()];
+        }
+        self
+    }
+
+    fn builtin_type_palette() -> Vec<TypePaletteEntry> {
+        PRIMITIVE_TYPES
+            .iter()
+            .map(|type_string| TypePaletteEntry {
+                display_name: type_string.to_string(),
+                icon: "type".to_string(),
+                type_string: type_string.to_string(),
+            })
+            .collect()
+    }
+
+    fn builtin_template() -> StructTemplate {
+        StructTemplate {
+            name: "Empty".to_string(),
+            fields: Vec::new(),
+        }
+    }
+
+    /// The template used when the user doesn't pick one explicitly.
+    pub fn default_template(&self) -> &StructTemplate {
+        &self.templates[0]
+    }
+
+    /// The built-in manifest, used before any project-specific one has been
+    /// loaded.
+    pub fn builtin() -> Self {
+        Self::default().merged_with_builtin()
+    }
+}
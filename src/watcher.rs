@@ -0,0 +1,42 @@
+//! Background watcher for a `.struct` definition's `struct.json`.
+//!
+//! Detects external edits (another editor on the same folder, a
+//! format-on-save tool, version control checking out a new revision) so
+//! [`crate::editor::StructEditor`] can reload or prompt to reconcile instead
+//! of silently drifting from what's on disk.
+
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::Path;
+use std::sync::mpsc::{channel, Receiver};
+
+/// Watches a single `struct.json` file for external modifications.
+pub struct FileWatcher {
+    _watcher: RecommendedWatcher,
+    receiver: Receiver<notify::Result<Event>>,
+}
+
+impl FileWatcher {
+    pub fn new(path: &Path) -> notify::Result<Self> {
+        let (tx, receiver) = channel();
+        let mut watcher = notify::recommended_watcher(move |event| {
+            let _ = tx.send(event);
+        })?;
+        watcher.watch(path, RecursiveMode::NonRecursive)?;
+        Ok(Self {
+            _watcher: watcher,
+            receiver,
+        })
+    }
+
+    /// Drains events observed since the last poll, returning whether the
+    /// file was modified or recreated.
+    pub fn poll_changed(&self) -> bool {
+        let mut changed = false;
+        while let Ok(Ok(event)) = self.receiver.try_recv() {
+            if event.kind.is_modify() || event.kind.is_create() {
+                changed = true;
+            }
+        }
+        changed
+    }
+}
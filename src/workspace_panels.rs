@@ -0,0 +1,392 @@
+//! The three panels that make up the struct editor workspace: properties,
+//! fields, and the generated-code preview.
+
+use gpui::*;
+use std::collections::{HashMap, VecDeque};
+use std::path::PathBuf;
+use std::process::ExitStatus;
+
+use crate::codegen::CodeGenTarget;
+use crate::editor::{FieldDef, StructModel};
+use crate::field_editor::{FieldEditorEvent, FieldEditorView, TypeCompletionCandidate};
+use crate::manifest::{FieldDefaults, TypePaletteEntry};
+
+/// Identifies a process spawned on behalf of the struct editor (e.g. a
+/// formatter or validator run against the current `struct.json`), so that
+/// concurrent runs can be demultiplexed. Plain `u32`, matching the id type
+/// `EditorInstance`'s process-lifecycle hooks pass (see `lib.rs`).
+pub type ProcessId = u32;
+
+// `ProcessHandle`/`SpawnRequest` cross the `EditorInstance` boundary by name
+// (see `lib.rs`'s process-lifecycle hooks), so — same as
+// `plugin_editor_api::PluginError` — they have to be the host crate's
+// own types rather than lookalikes defined in this plugin: a foreign
+// trait's method signatures are fixed at the crate that declares the
+// trait, and it can't have been written against a type this crate invents.
+pub use plugin_editor_api::{ProcessHandle, SpawnRequest};
+
+/// Tracks one external process run from request through completion.
+struct ProcessRun {
+    command: String,
+    handle: Option<ProcessHandle>,
+    output: Vec<u8>,
+    status: Option<ExitStatus>,
+}
+
+/// Displays and edits the struct's top-level properties (name, etc.).
+pub struct PropertiesPanel {
+    model: StructModel,
+    focus_handle: FocusHandle,
+}
+
+impl PropertiesPanel {
+    pub fn new(model: StructModel, _window: &mut Window, cx: &mut Context<Self>) -> Self {
+        Self {
+            model,
+            focus_handle: cx.focus_handle(),
+        }
+    }
+
+    pub fn set_model(&mut self, model: StructModel, cx: &mut Context<Self>) {
+        self.model = model;
+        cx.notify();
+    }
+}
+
+impl Focusable for PropertiesPanel {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for PropertiesPanel {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        div().flex().flex_col().child(self.model.name.clone())
+    }
+}
+
+/// Emitted by [`FieldsPanel`] whenever a field-mutating command changes its
+/// model, so [`crate::editor::StructEditor`] (the source of truth read by
+/// `plugin_save`/`is_dirty`/codegen) can pull the change back in and push it
+/// out to the other panels.
+#[derive(Debug, Clone, Copy)]
+pub enum FieldsPanelEvent {
+    ModelChanged,
+}
+
+/// Lists and edits the struct's fields.
+pub struct FieldsPanel {
+    model: StructModel,
+    workspace_root: PathBuf,
+    /// Allowed field types offered when adding a field, sourced from the
+    /// project's manifest (or the built-in primitives). Type-name completion
+    /// in `retype_field` does not use this palette; it offers
+    /// `PRIMITIVE_TYPES` and workspace struct names.
+    type_palette: Vec<TypePaletteEntry>,
+    /// Defaults applied to a newly added field.
+    field_defaults: FieldDefaults,
+    field_editors: Vec<Entity<FieldEditorView>>,
+    focus_handle: FocusHandle,
+}
+
+impl FieldsPanel {
+    pub fn new(
+        model: StructModel,
+        workspace_root: PathBuf,
+        type_palette: Vec<TypePaletteEntry>,
+        field_defaults: FieldDefaults,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let field_editors = Self::build_field_editors(&model, &workspace_root, cx);
+        Self {
+            model,
+            workspace_root,
+            type_palette,
+            field_defaults,
+            field_editors,
+            focus_handle: cx.focus_handle(),
+        }
+    }
+
+    pub fn set_model(&mut self, model: StructModel, cx: &mut Context<Self>) {
+        self.field_editors = Self::build_field_editors(&model, &self.workspace_root, cx);
+        self.model = model;
+        cx.notify();
+    }
+
+    pub fn model(&self) -> &StructModel {
+        &self.model
+    }
+
+    pub fn type_palette(&self) -> &[TypePaletteEntry] {
+        &self.type_palette
+    }
+
+    /// Re-runs type completion for the field named `name` as its type text
+    /// is edited (e.g. by a host-provided text input or the `retype-field`
+    /// command), applying `partial_text` as the field's in-progress type.
+    /// This is the one reachable path into
+    /// [`FieldEditorView::complete_type`] — without it the completion
+    /// subsystem is never invoked.
+    pub fn retype_field(
+        &mut self,
+        name: &str,
+        partial_text: String,
+        cursor_position: usize,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(index) = self.model.fields.iter().position(|field| field.name == name) else {
+            return;
+        };
+        if let Some(editor) = self.field_editors.get(index) {
+            editor.update(cx, |editor, cx| editor.edit_type(partial_text, cursor_position, cx));
+        }
+    }
+
+    /// The type completion candidates currently offered for the field named
+    /// `name`, from its last [`Self::retype_field`] call.
+    pub fn type_completions_for(&self, name: &str, cx: &App) -> Vec<TypeCompletionCandidate> {
+        let Some(index) = self.model.fields.iter().position(|field| field.name == name) else {
+            return Vec::new();
+        };
+        self.field_editors
+            .get(index)
+            .map(|editor| editor.read(cx).type_completions().to_vec())
+            .unwrap_or_default()
+    }
+
+    /// Adds a field named `name` with the palette entry's type string,
+    /// applying the manifest's field defaults (nullable, default value,
+    /// documentation).
+    pub fn add_field(&mut self, name: String, palette_entry: &TypePaletteEntry, cx: &mut Context<Self>) {
+        self.model.fields.push(FieldDef {
+            name,
+            field_type: palette_entry.type_string.clone(),
+            nullable: self.field_defaults.nullable,
+            default: self.field_defaults.default.clone(),
+            doc: self.field_defaults.doc.clone(),
+        });
+        self.field_editors = Self::build_field_editors(&self.model, &self.workspace_root, cx);
+        cx.emit(FieldsPanelEvent::ModelChanged);
+        cx.notify();
+    }
+
+    /// Removes the field named `name`, if present.
+    pub fn remove_field(&mut self, name: &str, cx: &mut Context<Self>) {
+        self.model.fields.retain(|field| field.name != name);
+        self.field_editors = Self::build_field_editors(&self.model, &self.workspace_root, cx);
+        cx.emit(FieldsPanelEvent::ModelChanged);
+        cx.notify();
+    }
+
+    /// Sorts fields alphabetically by name.
+    pub fn sort_fields(&mut self, cx: &mut Context<Self>) {
+        self.model.fields.sort_by(|a, b| a.name.cmp(&b.name));
+        self.field_editors = Self::build_field_editors(&self.model, &self.workspace_root, cx);
+        cx.emit(FieldsPanelEvent::ModelChanged);
+        cx.notify();
+    }
+
+    fn build_field_editors(
+        model: &StructModel,
+        workspace_root: &PathBuf,
+        cx: &mut Context<Self>,
+    ) -> Vec<Entity<FieldEditorView>> {
+        model
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(index, field)| {
+                let field = field.clone();
+                let workspace_root = workspace_root.clone();
+                let struct_name = model.name.clone();
+                let editor = cx.new(|cx| FieldEditorView::new(index, field, workspace_root, struct_name, cx));
+                // Fold per-field edits back into this panel's model and
+                // report them via `FieldsPanelEvent::ModelChanged`. The field
+                // editors are kept as they are (the one that raised the event
+                // already holds the edit); only a removal rebuilds them.
+                cx.subscribe(&editor, |this, field_editor, event, cx| {
+                    let index = field_editor.read(cx).field_index();
+                    match event {
+                        FieldEditorEvent::NameChanged(name) => {
+                            if let Some(field) = this.model.fields.get_mut(index) {
+                                field.name = name.clone();
+                                cx.emit(FieldsPanelEvent::ModelChanged);
+                                cx.notify();
+                            }
+                        }
+                        FieldEditorEvent::TypeChanged(field_type) => {
+                            if let Some(field) = this.model.fields.get_mut(index) {
+                                field.field_type = field_type.clone();
+                                cx.emit(FieldsPanelEvent::ModelChanged);
+                                cx.notify();
+                            }
+                        }
+                        FieldEditorEvent::NullableToggled(nullable) => {
+                            if let Some(field) = this.model.fields.get_mut(index) {
+                                field.nullable = *nullable;
+                                cx.emit(FieldsPanelEvent::ModelChanged);
+                                cx.notify();
+                            }
+                        }
+                        FieldEditorEvent::RemoveRequested => {
+                            if let Some(field) = this.model.fields.get(index) {
+                                let name = field.name.clone();
+                                this.remove_field(&name, cx);
+                            }
+                        }
+                    }
+                })
+                .detach();
+                editor
+            })
+            .collect()
+    }
+}
+
+impl EventEmitter<FieldsPanelEvent> for FieldsPanel {}
+
+impl Focusable for FieldsPanel {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for FieldsPanel {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .flex()
+            .flex_col()
+            .children(self.field_editors.iter().cloned())
+    }
+}
+
+/// Renders a live preview of generated code for the struct, and pipes it
+/// through external tools (formatters, validators, codegen binaries) on
+/// request.
+pub struct CodePreviewPanel {
+    model: StructModel,
+    target: CodeGenTarget,
+    runs: HashMap<ProcessId, ProcessRun>,
+    pending_spawns: Vec<SpawnRequest>,
+    /// Commands for spawns already handed to the host (via
+    /// `take_pending_spawns`) but not yet reported back as actually spawned.
+    /// `on_process_spawned` only gets an id/handle from the host, so this is
+    /// how a run's command is recovered: the host is expected to report
+    /// spawns back in the same order it drained them, so a FIFO pop lines
+    /// each report back up with the request that produced it.
+    pending_commands: VecDeque<String>,
+    focus_handle: FocusHandle,
+}
+
+impl CodePreviewPanel {
+    pub fn new(model: StructModel, _window: &mut Window, cx: &mut Context<Self>) -> Self {
+        Self {
+            model,
+            target: CodeGenTarget::Rust,
+            runs: HashMap::new(),
+            pending_spawns: Vec::new(),
+            pending_commands: VecDeque::new(),
+            focus_handle: cx.focus_handle(),
+        }
+    }
+
+    pub fn set_model(&mut self, model: StructModel, cx: &mut Context<Self>) {
+        self.model = model;
+        cx.notify();
+    }
+
+    pub fn target(&self) -> CodeGenTarget {
+        self.target
+    }
+
+    pub fn set_target(&mut self, target: CodeGenTarget, cx: &mut Context<Self>) {
+        self.target = target;
+        cx.notify();
+    }
+
+    /// The active generator's output for the current model, used for the
+    /// live preview, copy-to-clipboard, and as input to the subprocess
+    /// pipeline (e.g. "generate then run an external compiler to check it").
+    pub fn active_output(&self) -> String {
+        self.target.generate(&self.model)
+    }
+
+    pub fn copy_active_output_to_clipboard(&self, cx: &mut Context<Self>) {
+        cx.write_to_clipboard(ClipboardItem::new_string(self.active_output()));
+    }
+
+    /// Queues a request to run `command` (e.g. a formatter or schema
+    /// validator) against the current struct. The host picks this up via
+    /// [`Self::take_pending_spawns`] and reports back through
+    /// `on_process_spawned`/`on_process_output`/`on_process_exit`.
+    pub fn request_run(&mut self, command: impl Into<String>, args: Vec<String>, cx: &mut Context<Self>) {
+        let command = command.into();
+        self.pending_commands.push_back(command.clone());
+        self.pending_spawns.push(SpawnRequest { command, args });
+        cx.notify();
+    }
+
+    /// Drains queued spawn requests for the host to act on.
+    pub fn take_pending_spawns(&mut self) -> Vec<SpawnRequest> {
+        std::mem::take(&mut self.pending_spawns)
+    }
+
+    pub fn on_process_spawned(&mut self, id: ProcessId, handle: ProcessHandle, cx: &mut Context<Self>) {
+        let command = self.pending_commands.pop_front().unwrap_or_default();
+        self.runs.insert(
+            id,
+            ProcessRun {
+                command,
+                handle: Some(handle),
+                output: Vec::new(),
+                status: None,
+            },
+        );
+        cx.notify();
+    }
+
+    pub fn on_process_output(&mut self, id: ProcessId, data: &[u8], cx: &mut Context<Self>) {
+        if let Some(run) = self.runs.get_mut(&id) {
+            run.output.extend_from_slice(data);
+            cx.notify();
+        }
+    }
+
+    pub fn on_process_exit(&mut self, id: ProcessId, status: ExitStatus, cx: &mut Context<Self>) {
+        if let Some(run) = self.runs.get_mut(&id) {
+            run.status = Some(status);
+            cx.notify();
+        }
+    }
+}
+
+impl Focusable for CodePreviewPanel {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for CodePreviewPanel {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .flex()
+            .flex_col()
+            .child(
+                div()
+                    .flex()
+                    .gap_2()
+                    .children(CodeGenTarget::ALL.iter().map(|target| target.display_name())),
+            )
+            .child(div().child(self.active_output()))
+            .children(self.runs.values().map(|run| {
+                let status = match &run.status {
+                    Some(status) if status.success() => "ok".to_string(),
+                    Some(status) => format!("failed ({status})"),
+                    None => "running".to_string(),
+                };
+                format!("{}: {}", run.command, status)
+            }))
+    }
+}